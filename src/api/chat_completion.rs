@@ -1,8 +1,6 @@
-use std::default;
-
 use serde::{de, Deserialize, Serialize};
 
-use crate::IntoRequest;
+use crate::{IntoRequest, Result};
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Serialize, Builder)]
@@ -34,7 +32,7 @@ pub struct ChatCompletionRequest {
     /// Setting to { "type": "json_object" } enables JSON mode, which guarantees the message the model generates is valid JSON.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
-    response_format: Option<ChatResponseFormatObject>,
+    response_format: Option<ChatResponseFormat>,
     /// This feature is in Beta. If specified, our system will make a best effort to sample deterministically, such that repeated requests with the same seed and parameters should return the same result. Determinism is not guaranteed, and you should refer to the system_fingerprint response parameter to monitor changes in the backend.
     #[builder(default, setter(strip_option))]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,7 +61,7 @@ pub struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     tools: Vec<Tool>,
     /// Controls which (if any) function is called by the model. none means the model will not call a function and instead generates a message. auto means the model can pick between generating a message or calling a function. Specifying a particular function via {"type: "function", "function": {"name": "my_function"}} forces the model to call that function.
-    #[builder(default, setter(strip_option))]
+    #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<ToolChoice>,
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
@@ -72,8 +70,21 @@ pub struct ChatCompletionRequest {
     user: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
-#[serde(rename_all = "snake_case")]
+impl ChatCompletionRequestBuilder {
+    /// Force the model to emit output that validates against `schema`, named `name`.
+    pub fn json_schema_output(&mut self, name: impl Into<String>, schema: serde_json::Value) -> &mut Self {
+        self.response_format = Some(Some(ChatResponseFormat::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: name.into(),
+                strict: true,
+                schema,
+            },
+        }));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ToolChoice {
     #[default]
     None,
@@ -84,6 +95,95 @@ pub enum ToolChoice {
     },
 }
 
+impl Serialize for ToolChoice {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // Matches OpenAI's wire shape: {"type":"function","function":{"name":...}}.
+        #[derive(Serialize)]
+        struct Wire<'a> {
+            r#type: ToolType,
+            function: WireFunction<'a>,
+        }
+        #[derive(Serialize)]
+        struct WireFunction<'a> {
+            name: &'a str,
+        }
+
+        match self {
+            ToolChoice::None => serializer.serialize_str("none"),
+            ToolChoice::Auto => serializer.serialize_str("auto"),
+            ToolChoice::Function { r#type, name } => Wire {
+                r#type: *r#type,
+                function: WireFunction { name },
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ToolChoice {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        // Accepts both the OpenAI wire shape ({"type":"function","function":{"name":...}},
+        // also produced by this type's own `Serialize` impl) and the externally-tagged
+        // shape ({"function":{"type":...,"name":...}}) some older callers may still send.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Str(String),
+            Wire {
+                r#type: ToolType,
+                function: ToolChoiceFunctionName,
+            },
+            SelfTagged {
+                function: SelfTaggedFunction,
+            },
+        }
+
+        #[derive(Deserialize)]
+        struct ToolChoiceFunctionName {
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        struct SelfTaggedFunction {
+            r#type: ToolType,
+            name: String,
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Str(s) if s == "none" => Ok(ToolChoice::None),
+            Repr::Str(s) if s == "auto" => Ok(ToolChoice::Auto),
+            Repr::Str(s) => Err(de::Error::unknown_variant(&s, &["none", "auto"])),
+            Repr::Wire { r#type, function } => Ok(ToolChoice::Function {
+                r#type,
+                name: function.name,
+            }),
+            Repr::SelfTagged { function } => Ok(ToolChoice::Function {
+                r#type: function.r#type,
+                name: function.name,
+            }),
+        }
+    }
+}
+
+impl From<&str> for ToolChoice {
+    fn from(name: &str) -> Self {
+        match name {
+            "none" => ToolChoice::None,
+            "auto" => ToolChoice::Auto,
+            _ => ToolChoice::Function {
+                r#type: ToolType::Function,
+                name: name.to_string(),
+            },
+        }
+    }
+}
+
+impl From<String> for ToolChoice {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Tool {
     /// The type of the tool. Currently, only function is supported.
@@ -103,18 +203,35 @@ pub struct FunctionInfo {
     parameters: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct ChatResponseFormatObject {
-    r#type: ChatResponseFormat,
-}
-
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 /// tag 指定生成字段名称
 pub enum ChatResponseFormat {
     Text,
     #[default]
     Json,
+    /// Force the model to emit output that validates against a JSON Schema.
+    JsonSchema { json_schema: JsonSchemaFormat },
+    /// Force the model to emit output that satisfies a formal grammar.
+    Grammar { grammar: GrammarType },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonSchemaFormat {
+    /// A name identifying the schema, used by the model to reference it.
+    pub name: String,
+    /// Whether to enforce strict adherence to the schema.
+    pub strict: bool,
+    /// The JSON Schema the response must validate against.
+    pub schema: serde_json::Value,
+}
+
+/// A formal grammar a generation must satisfy: either a JSON Schema or a regular expression.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum GrammarType {
+    Json(serde_json::Value),
+    Regex(String),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -133,18 +250,47 @@ pub enum ChatCompletionMessage {
     // Function(FunctionMessage),
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ChatCompleteModel {
     #[default]
-    #[serde(rename = "gpt-3.5-turbo-1106")]
     GPT3Turbo,
-    #[serde(rename = "gpt-3.5-turbo-instruct")]
     GPT3TurboInstruct,
-    #[serde(rename = "gpt-4-1106-preview")]
     GPT4Turbo,
-    #[serde(rename = "gpt-4-1106-vision-preview")]
     GPT4TurboVersion,
+    /// A free-form model name, for OpenAI-compatible backends (self-hosted, Azure, etc.)
+    /// that accept arbitrary model identifiers.
+    Custom(String),
+}
+
+impl ChatCompleteModel {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::GPT3Turbo => "gpt-3.5-turbo-1106",
+            Self::GPT3TurboInstruct => "gpt-3.5-turbo-instruct",
+            Self::GPT4Turbo => "gpt-4-1106-preview",
+            Self::GPT4TurboVersion => "gpt-4-1106-vision-preview",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
+impl Serialize for ChatCompleteModel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatCompleteModel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "gpt-3.5-turbo-1106" => Self::GPT3Turbo,
+            "gpt-3.5-turbo-instruct" => Self::GPT3TurboInstruct,
+            "gpt-4-1106-preview" => Self::GPT4Turbo,
+            "gpt-4-1106-vision-preview" => Self::GPT4TurboVersion,
+            _ => Self::Custom(name),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Builder)]
@@ -159,12 +305,71 @@ pub struct SystemMessage {
 #[derive(Debug, Clone, Serialize, Builder)]
 pub struct UserMessage {
     /// The contents of the user message
-    content: String,
+    #[builder(setter(into))]
+    content: Content,
     /// An optional name for the participant. Provides the model information to differentiate between participants of the same role.
     #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
 }
 
+/// The contents of a user message: plain text, or a mix of text and image parts for vision models.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Content {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+impl From<&str> for Content {
+    fn from(value: &str) -> Self {
+        Content::Text(value.into())
+    }
+}
+
+impl From<String> for Content {
+    fn from(value: String) -> Self {
+        Content::Text(value)
+    }
+}
+
+impl Content {
+    /// Flatten to plain text, concatenating the `text` parts and dropping any images.
+    pub fn as_text(&self) -> String {
+        match self {
+            Content::Text(text) => text.clone(),
+            Content::Parts(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text } => Some(text.as_str()),
+                    ContentPart::ImageUrl { .. } => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageUrl {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<Detail>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Detail {
+    Auto,
+    Low,
+    High,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssistantMessage {
     /// The contents of the assistant message
@@ -196,13 +401,42 @@ pub struct ToolCalls {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct FunctionCall {
+pub struct FunctionCall {
     /// The name of the function to call.
     name: String,
     /// The arguments to call the function with, as generated by the model in JSON format.
     arguments: String,
 }
 
+impl ToolCalls {
+    /// The ID of the tool call.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The type of the tool. Currently, only function is supported.
+    pub fn r#type(&self) -> ToolType {
+        self.r#type
+    }
+
+    /// The function that the model called.
+    pub fn function(&self) -> &FunctionCall {
+        &self.function
+    }
+}
+
+impl FunctionCall {
+    /// The name of the function to call.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Parse the model-generated JSON arguments into `T`.
+    pub fn arguments<T: de::DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.arguments)?)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolType {
@@ -260,10 +494,70 @@ pub enum FinishReason {
     ToolCalls,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// A unique identifier for the chat completion. Each chunk has the same ID.
+    pub id: String,
+    /// A list of chat completion choices. Can be more than one if n is greater than 1.
+    pub choices: Vec<ChatCompletionChunkChoice>,
+    /// The Unix timestamp (in seconds) of when the chat completion was created.
+    pub created: usize,
+    /// The model used for the chat completion.
+    pub model: ChatCompleteModel,
+    /// This fingerprint represents the backend configuration that the model runs with.
+    pub system_fingerprint: String,
+    /// The object type, which is always chat.completion.chunk.
+    pub object: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionChunkChoice {
+    /// A chat completion delta generated by streamed model responses.
+    pub delta: AssistantMessageDelta,
+    /// The reason the model stopped generating tokens. Only present on the final chunk for this choice.
+    pub finish_reason: Option<FinishReason>,
+    /// The index of the choice in the list of choices.
+    pub index: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssistantMessageDelta {
+    /// The incremental content for this chunk, if any.
+    pub content: Option<String>,
+    /// The incremental tool calls for this chunk, if any. Callers fold these by `index` across chunks.
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCallDelta>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolCallDelta {
+    /// The index of the tool call this delta applies to, used to fold deltas across chunks.
+    pub index: usize,
+    pub id: Option<String>,
+    pub r#type: Option<ToolType>,
+    pub function: Option<FunctionCallDelta>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionCallDelta {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+impl ChatCompletionRequest {
+    /// Force `stream` to `stream`. Used by `LLmSdk::chat_completion_stream` since the
+    /// field is private to this module.
+    pub(crate) fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+}
+
 impl IntoRequest for ChatCompletionRequest {
-    fn into_request(self, client: reqwest::Client) -> reqwest::RequestBuilder {
+    fn into_request(self, client: reqwest::Client, base_url: &str) -> reqwest::RequestBuilder {
         client
-            .post("https://api.openai.com/v1/chat/completions")
+            .post(format!("{base_url}/chat/completions"))
             .json(&self)
     }
 }
@@ -276,17 +570,57 @@ impl ChatCompletionMessage {
         })
     }
 
-    pub fn new_user(content: impl Into<String>, name: &str) -> ChatCompletionMessage {
+    pub fn new_user(content: impl Into<Content>, name: &str) -> ChatCompletionMessage {
         ChatCompletionMessage::User(UserMessage {
             content: content.into(),
             name: Self::get_name(name),
         })
     }
 
+    pub fn new_user_with_images(
+        content: impl Into<String>,
+        urls: impl IntoIterator<Item = impl Into<String>>,
+        name: &str,
+    ) -> ChatCompletionMessage {
+        let mut parts = vec![ContentPart::Text {
+            text: content.into(),
+        }];
+        parts.extend(urls.into_iter().map(|url| ContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: url.into(),
+                detail: None,
+            },
+        }));
+        ChatCompletionMessage::User(UserMessage {
+            content: Content::Parts(parts),
+            name: Self::get_name(name),
+        })
+    }
+
     #[inline]
     fn get_name(name: &str) -> Option<String> {
         (!name.is_empty()).then(|| name.into())
     }
+
+    /// The message's role as used by chat templates and the wire format: "system", "user", etc.
+    pub fn role(&self) -> &'static str {
+        match self {
+            ChatCompletionMessage::System(_) => "system",
+            ChatCompletionMessage::User(_) => "user",
+            ChatCompletionMessage::Assistant(_) => "assistant",
+            ChatCompletionMessage::Tool(_) => "tool",
+        }
+    }
+
+    /// The message content flattened to plain text, collapsing multimodal parts to their text.
+    pub fn text_content(&self) -> String {
+        match self {
+            ChatCompletionMessage::System(m) => m.content.clone(),
+            ChatCompletionMessage::User(m) => m.content.as_text(),
+            ChatCompletionMessage::Assistant(m) => m.content.clone(),
+            ChatCompletionMessage::Tool(m) => m.content.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -309,8 +643,8 @@ mod tests {
             json,
             serde_json::json!({
                 "tool_choice":{
+                    "type":"function",
                     "function":{
-                        "type":"function",
                         "name":"my_function"
                     }
                 },
@@ -340,13 +674,20 @@ mod tests {
         assert_eq!(
             json,
             serde_json::json!({
-                "tool_choice":{
-                    "function":{
-                        "type":"function",
-                        "name":"my_function"
+                "tool_choice": "auto",
+                "model": "gpt-3.5-turbo-1106",
+                "messages":[
+                    {
+                        "role": "system",
+                        "content": "我可以回答你问我的任何问题.",
+                        "name": "Q-bot"
+                    },
+                    {
+                        "role": "user",
+                        "content": "什么是生活?",
+                        "name": "zheng"
                     }
-                },
-                "messages":[]
+                ]
             })
         );
     }
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ChatCompletionUsage, ChatCompleteModel, FinishReason, IntoRequest};
+use derive_builder::Builder;
+
+#[derive(Debug, Clone, Serialize, Builder)]
+pub struct CompletionRequest {
+    /// The prompt(s) to generate completions for, encoded as a string or array of strings.
+    #[builder(setter(into))]
+    prompt: Prompt,
+    /// ID of the model to use. See the model endpoint compatibility table for details on which models work with the Completions API.
+    #[builder(default)]
+    model: ChatCompleteModel,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on their existing frequency in the text so far, decreasing the model's likelihood to repeat the same line verbatim.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<i32>,
+    /// The maximum number of tokens to generate in the completion.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+    /// Include the log probabilities on the logprobs most likely tokens.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logprobs: Option<usize>,
+    /// How many completions to generate for each prompt. Note that you will be charged based on the number of generated tokens across all of the choices. Keep n as 1 to minimize costs.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<usize>,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far, increasing the model's likelihood to talk about new topics.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<usize>,
+    /// If specified, our system will make a best effort to sample deterministically, such that repeated requests with the same seed and parameters should return the same result.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<usize>,
+    /// Up to 4 sequences where the API will stop generating further tokens.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<String>,
+    /// Whether to stream back partial progress as data-only server-sent events.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<i32>,
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass.
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<i32>,
+}
+
+/// The prompt(s) to complete, either a single string or a batch of them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Prompt {
+    Text(String),
+    Texts(Vec<String>),
+}
+
+impl From<&str> for Prompt {
+    fn from(value: &str) -> Self {
+        Prompt::Text(value.into())
+    }
+}
+
+impl From<String> for Prompt {
+    fn from(value: String) -> Self {
+        Prompt::Text(value)
+    }
+}
+
+impl From<Vec<String>> for Prompt {
+    fn from(value: Vec<String>) -> Self {
+        Prompt::Texts(value)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionResponse {
+    /// A unique identifier for the completion.
+    pub id: String,
+    /// The list of completion choices the model generated for the input prompt.
+    pub choices: Vec<CompletionChoice>,
+    /// The Unix timestamp (in seconds) of when the completion was created.
+    pub created: usize,
+    /// The model used for the completion.
+    pub model: ChatCompleteModel,
+    /// The object type, which is always text_completion.
+    pub object: String,
+    /// Usage statistics for the completion request.
+    pub usage: ChatCompletionUsage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionChoice {
+    /// The generated completion text.
+    pub text: String,
+    /// The index of the choice in the list of choices.
+    pub index: usize,
+    /// Log probability information for the choice, if requested.
+    pub logprobs: Option<serde_json::Value>,
+    /// The reason the model stopped generating tokens.
+    pub finish_reason: FinishReason,
+}
+
+impl IntoRequest for CompletionRequest {
+    fn into_request(self, client: reqwest::Client, base_url: &str) -> reqwest::RequestBuilder {
+        client.post(format!("{base_url}/completions")).json(&self)
+    }
+}
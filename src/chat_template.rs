@@ -0,0 +1,62 @@
+use minijinja::{context, Environment};
+
+use crate::{ChatCompletionMessage, Result};
+
+/// A ChatML-style default, used when no model-specific template is supplied.
+const DEFAULT_TEMPLATE: &str = "\
+{%- for message in messages -%}
+<|im_start|>{{ message.role }}
+{{ message.content }}<|im_end|>
+{% endfor -%}
+{%- if add_generation_prompt -%}
+<|im_start|>assistant
+{% endif -%}";
+
+/// Renders a list of `ChatCompletionMessage`s into a single prompt string using a
+/// per-model Jinja chat template, mirroring how HF text-generation servers apply
+/// the `tokenizer_config.json` chat template.
+pub struct ChatTemplate {
+    env: Environment<'static>,
+    bos_token: String,
+    eos_token: String,
+}
+
+impl ChatTemplate {
+    /// Compile `template` with the given `bos_token`/`eos_token` made available in its context.
+    pub fn new(
+        template: impl Into<String>,
+        bos_token: impl Into<String>,
+        eos_token: impl Into<String>,
+    ) -> Result<Self> {
+        let mut env = Environment::new();
+        env.add_template_owned("chat", template.into())?;
+        Ok(Self {
+            env,
+            bos_token: bos_token.into(),
+            eos_token: eos_token.into(),
+        })
+    }
+
+    /// Flatten `messages` into a single prompt string. When `add_generation_prompt` is set,
+    /// the template appends the tokens that cue the model to start generating a reply.
+    pub fn render(&self, messages: &[ChatCompletionMessage], add_generation_prompt: bool) -> Result<String> {
+        let messages: Vec<_> = messages
+            .iter()
+            .map(|m| context! { role => m.role(), content => m.text_content() })
+            .collect();
+        let tmpl = self.env.get_template("chat")?;
+        let rendered = tmpl.render(context! {
+            messages,
+            add_generation_prompt,
+            bos_token => self.bos_token,
+            eos_token => self.eos_token,
+        })?;
+        Ok(rendered)
+    }
+}
+
+impl Default for ChatTemplate {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEMPLATE, "<s>", "</s>").expect("default chat template must compile")
+    }
+}
@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+/// Errors that can occur while calling the chat or text completion APIs, or while
+/// rendering a chat template locally.
+#[derive(Debug, Error)]
+pub enum SdkError {
+    #[error("Request failed: {0}")]
+    ReqwestError(#[from] reqwest::Error),
+    #[error("Failed to parse response: {0}")]
+    SerdeError(#[from] serde_json::Error),
+    #[error("Failed to render chat template: {0}")]
+    TemplateError(#[from] minijinja::Error),
+}
+
+pub type Result<T> = std::result::Result<T, SdkError>;
@@ -0,0 +1,123 @@
+mod chat_template;
+mod error;
+
+pub mod api;
+
+pub use api::*;
+pub use chat_template::*;
+pub use error::*;
+
+use futures::{Stream, StreamExt};
+use reqwest::{Client, RequestBuilder, Response};
+use std::pin::Pin;
+
+/// Builds the final `reqwest::RequestBuilder` for a concrete API request type, against
+/// whichever `base_url` the client was configured with.
+pub trait IntoRequest {
+    fn into_request(self, client: Client, base_url: &str) -> RequestBuilder;
+}
+
+const API_BASE: &str = "https://api.openai.com/v1";
+
+/// A thin, stateless client for the OpenAI chat completion API, or any backend
+/// (self-hosted, Azure, TGI, vLLM, ...) that speaks the same schema.
+#[derive(Debug, Clone)]
+pub struct LLmSdk {
+    client: Client,
+    base_url: String,
+}
+
+impl LLmSdk {
+    /// Create a client authenticated with `token` against the official OpenAI API.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self::with_base_url(token, API_BASE)
+    }
+
+    /// Create a client authenticated with `token` against a custom, OpenAI-compatible `base_url`.
+    pub fn with_base_url(token: impl Into<String>, base_url: impl Into<String>) -> Self {
+        let client = Client::builder()
+            .default_headers(Self::headers(&token.into()))
+            .build()
+            .expect("Failed to build reqwest client");
+        Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn headers(token: &str) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        let mut auth = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .expect("Failed to build auth header");
+        auth.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, auth);
+        headers
+    }
+
+    /// Send a chat completion request and wait for the fully buffered response.
+    pub async fn chat_completion(&self, req: ChatCompletionRequest) -> Result<ChatCompletionResponse> {
+        let res = req
+            .into_request(self.client.clone(), &self.base_url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(res.json::<ChatCompletionResponse>().await?)
+    }
+
+    /// Send a legacy text completion request and wait for the fully buffered response.
+    pub async fn completion(&self, req: CompletionRequest) -> Result<CompletionResponse> {
+        let res = req
+            .into_request(self.client.clone(), &self.base_url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(res.json::<CompletionResponse>().await?)
+    }
+
+    /// Send a chat completion request with `stream` forced to `true` and fold the
+    /// server-sent events into a `Stream` of incremental `ChatCompletionChunk`s.
+    pub async fn chat_completion_stream(
+        &self,
+        req: ChatCompletionRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk>> + Send>>> {
+        let req = req.with_stream(true);
+        let res = req
+            .into_request(self.client.clone(), &self.base_url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(sse_stream(res))
+    }
+}
+
+/// Folds a `reqwest::Response` body made of `data: ...\n\n` SSE events into a
+/// stream of deserialized payloads, terminating cleanly on `data: [DONE]`.
+fn sse_stream(res: Response) -> Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk>> + Send>> {
+    Box::pin(async_stream::try_stream! {
+        let mut bytes = res.bytes_stream();
+        let mut buf = Vec::new();
+        while let Some(chunk) = bytes.next().await {
+            buf.extend_from_slice(&chunk?);
+            while let Some(pos) = find_subslice(&buf, b"\n\n") {
+                let event: Vec<u8> = buf.drain(..pos + 2).collect();
+                let Some(payload) = parse_sse_payload(&event) else { continue };
+                if payload == "[DONE]" {
+                    return;
+                }
+                yield serde_json::from_str::<ChatCompletionChunk>(&payload)?;
+            }
+        }
+    })
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn parse_sse_payload(event: &[u8]) -> Option<String> {
+    let event = String::from_utf8_lossy(event);
+    event
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .map(|data| data.trim().to_string())
+}